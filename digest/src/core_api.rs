@@ -0,0 +1,112 @@
+//! Low-level traits for implementing block-buffered Message Authentication Codes.
+//!
+//! Many MAC algorithms (CMAC, PMAC, CBC-MAC, HMAC over a block hash) are
+//! naturally expressed as a fixed-block-size core plus generic
+//! buffering/padding logic. [`MacCore`] captures just the cryptographic core,
+//! while [`CoreWrapper`] supplies the block buffering needed to implement the
+//! full [`Mac`](crate::Mac) surface.
+
+use crate::{FixedOutput, FixedOutputReset, MacMarker, Output, Update};
+use block_buffer::{BlockBuffer, Lazy};
+use crypto_common::{Block, BlockSizeUser, InvalidLength, Key, KeyInit, KeySizeUser, OutputSizeUser, Reset};
+
+/// Core trait for block-buffered Message Authentication Code implementations.
+///
+/// Implementors provide only the cryptographic core: key initialization, how
+/// to absorb full blocks, and how to finalize using the remaining data held
+/// in the managed [`BlockBuffer`]. [`CoreWrapper`] takes care of buffering
+/// partial blocks, so implementors never handle `Update`'s partial-block
+/// bookkeeping by hand.
+#[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
+pub trait MacCore: BlockSizeUser + KeySizeUser + OutputSizeUser + Sized {
+    /// Create new core value from the fixed size key.
+    fn new(key: &Key<Self>) -> Self;
+
+    /// Create new core value from a variable size key.
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength>;
+
+    /// Update state using the provided full-size blocks.
+    fn update_blocks(&mut self, blocks: &[Block<Self>]);
+
+    /// Finalize the core using the trailing data held by `buffer` and return
+    /// the resulting tag. The buffer is lazy, so this data may be a complete
+    /// block: a final full block is kept here instead of being passed to
+    /// [`update_blocks`][Self::update_blocks].
+    fn finalize(&mut self, buffer: &mut BlockBuffer<Self::BlockSize, Lazy>) -> Output<Self>;
+}
+
+/// Wrapper around a [`MacCore`] implementation which manages a [`BlockBuffer`]
+/// on its behalf, giving it the full [`Mac`](crate::Mac) surface via the
+/// blanket impl over [`KeyInit`] + [`Update`] + [`FixedOutput`] +
+/// [`MacMarker`].
+#[derive(Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
+pub struct CoreWrapper<T: MacCore> {
+    core: T,
+    buffer: BlockBuffer<T::BlockSize, Lazy>,
+}
+
+impl<T: MacCore> CoreWrapper<T> {
+    /// Create a new wrapper from a core value that has already been
+    /// initialized with a key.
+    #[inline(always)]
+    pub fn from_core(core: T) -> Self {
+        Self {
+            core,
+            buffer: Default::default(),
+        }
+    }
+}
+
+impl<T: MacCore> MacMarker for CoreWrapper<T> {}
+
+impl<T: MacCore> KeySizeUser for CoreWrapper<T> {
+    type KeySize = T::KeySize;
+}
+
+impl<T: MacCore> OutputSizeUser for CoreWrapper<T> {
+    type OutputSize = T::OutputSize;
+}
+
+impl<T: MacCore> KeyInit for CoreWrapper<T> {
+    #[inline]
+    fn new(key: &Key<Self>) -> Self {
+        Self::from_core(T::new(key))
+    }
+
+    #[inline]
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        T::new_from_slice(key).map(Self::from_core)
+    }
+}
+
+impl<T: MacCore> Update for CoreWrapper<T> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        let Self { core, buffer } = self;
+        buffer.digest_blocks(data, |blocks| core.update_blocks(blocks));
+    }
+}
+
+impl<T: MacCore> FixedOutput for CoreWrapper<T> {
+    #[inline]
+    fn finalize_into(mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.core.finalize(&mut self.buffer));
+    }
+}
+
+impl<T: MacCore + Reset> Reset for CoreWrapper<T> {
+    #[inline]
+    fn reset(&mut self) {
+        self.core.reset();
+        self.buffer.reset();
+    }
+}
+
+impl<T: MacCore + Reset> FixedOutputReset for CoreWrapper<T> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.core.finalize(&mut self.buffer));
+        Reset::reset(self);
+    }
+}
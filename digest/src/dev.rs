@@ -0,0 +1,14 @@
+//! Development-related functionality.
+//!
+//! This module is intended to be used by third-party crates for testing
+//! [`Mac`](crate::Mac) implementations against standard test vectors. It is
+//! hidden behind the `dev` feature since it's intended to be used only by
+//! developers of such crates.
+
+#[cfg(feature = "mac")]
+mod mac;
+
+#[cfg(feature = "mac")]
+pub use mac::*;
+
+pub use blobby;
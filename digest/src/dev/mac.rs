@@ -0,0 +1,118 @@
+/// Define a [`Mac`](crate::Mac) test using a blobby-encoded `(key, input, tag)`
+/// set of test vectors.
+///
+/// The generated test decodes `$data` as a flat sequence of byte-triples and,
+/// for each triple, exercises the full [`Mac`](crate::Mac) surface:
+///
+/// 1. one-shot computation via a single `update` call;
+/// 2. the same computation, but with `input` fed one byte at a time, to catch
+///    buffering bugs;
+/// 3. `finalize_reset` followed by a repeat computation, to confirm state is
+///    correctly cleared;
+/// 4. round-tripping the tag through `verify_slice`, `verify_truncated_left`,
+///    and `verify_truncated_right`, with both the correct tag and a
+///    deliberately corrupted one.
+#[macro_export]
+macro_rules! new_mac_test {
+    ($name:ident, $mac_ty:ty, $data:expr) => {
+        #[test]
+        fn $name() {
+            use $crate::dev::blobby::Blob3Iterator;
+            use $crate::Mac;
+
+            fn run_test(key: &[u8], input: &[u8], tag: &[u8]) -> Option<&'static str> {
+                macro_rules! new_mac {
+                    () => {
+                        match <$mac_ty as Mac>::new_from_slice(key) {
+                            Ok(mac) => mac,
+                            Err(_) => return Some("key construction failed"),
+                        }
+                    };
+                }
+
+                let mut mac = new_mac!();
+                mac.update(input);
+                if mac.verify_slice(tag).is_err() {
+                    return Some("one-shot update");
+                }
+
+                let mut mac = new_mac!();
+                for byte in input {
+                    mac.update(core::slice::from_ref(byte));
+                }
+                if mac.verify_slice(tag).is_err() {
+                    return Some("byte-at-a-time update");
+                }
+
+                let mut mac = new_mac!();
+                mac.update(input);
+                let first_tag = mac.finalize_reset().into_bytes();
+                if first_tag.as_slice() != tag {
+                    return Some("finalize_reset: first pass tag mismatch");
+                }
+                mac.update(input);
+                if mac.finalize().into_bytes() != first_tag {
+                    return Some("finalize_reset: state not cleared");
+                }
+
+                let mut bad_tag = tag.to_vec();
+                bad_tag[0] ^= 0x01;
+
+                let mut valid = new_mac!();
+                valid.update(input);
+                let mut corrupted = new_mac!();
+                corrupted.update(input);
+                if valid.verify_slice(tag).is_err() {
+                    return Some("verify_slice: valid tag rejected");
+                }
+                if corrupted.verify_slice(&bad_tag).is_ok() {
+                    return Some("verify_slice: corrupted tag accepted");
+                }
+
+                for n in 1..tag.len() {
+                    let left = &tag[..n];
+                    let mut bad_left = left.to_vec();
+                    bad_left[n - 1] ^= 0x01;
+
+                    let mut valid = new_mac!();
+                    valid.update(input);
+                    let mut corrupted = new_mac!();
+                    corrupted.update(input);
+                    if valid.verify_truncated_left(left).is_err() {
+                        return Some("verify_truncated_left: valid tag rejected");
+                    }
+                    if corrupted.verify_truncated_left(&bad_left).is_ok() {
+                        return Some("verify_truncated_left: corrupted tag accepted");
+                    }
+
+                    let right = &tag[tag.len() - n..];
+                    let mut bad_right = right.to_vec();
+                    bad_right[0] ^= 0x01;
+
+                    let mut valid = new_mac!();
+                    valid.update(input);
+                    let mut corrupted = new_mac!();
+                    corrupted.update(input);
+                    if valid.verify_truncated_right(right).is_err() {
+                        return Some("verify_truncated_right: valid tag rejected");
+                    }
+                    if corrupted.verify_truncated_right(&bad_right).is_ok() {
+                        return Some("verify_truncated_right: corrupted tag accepted");
+                    }
+                }
+
+                None
+            }
+
+            for (i, row) in Blob3Iterator::new($data).unwrap().enumerate() {
+                let [key, input, tag] = row.unwrap();
+                if let Some(desc) = run_test(key, input, tag) {
+                    panic!(
+                        "\n Failed test #{} ({})\n  key:\t{:?}\n  input:\t{:?}\n  tag:\t{:?}\n",
+                        i, desc, key, input, tag,
+                    );
+                }
+            }
+        }
+    };
+}
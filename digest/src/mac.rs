@@ -1,10 +1,13 @@
-use crate::{FixedOutput, FixedOutputReset, Update};
+use crate::{FixedOutput, FixedOutputReset, Update, XofReader};
 use crypto_common::{InvalidLength, Key, KeyInit, KeySizeUser, Output, OutputSizeUser, Reset};
 
 use core::fmt;
-use generic_array::typenum::Unsigned;
+use generic_array::{typenum::Unsigned, GenericArray};
 use subtle::{Choice, ConstantTimeEq};
 
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+
 /// Marker trait for Message Authentication algorithms.
 #[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
 pub trait MacMarker {}
@@ -220,3 +223,141 @@ impl fmt::Display for MacError {
 
 #[cfg(feature = "std")]
 impl std::error::Error for MacError {}
+
+/// Marker trait for Message Authentication algorithms with a variable
+/// (runtime-selected) output size, e.g. KMAC128/256.
+#[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
+pub trait VariableOutputMacMarker: KeySizeUser {}
+
+/// Convenience wrapper trait covering functionality of Message Authentication
+/// algorithms whose tag length is chosen at construction time rather than
+/// fixed by the type, such as KMAC128/256 or keyed cSHAKE.
+///
+/// Unlike [`Mac`], which always produces [`Self::OutputSize`][OutputSizeUser::OutputSize]
+/// bytes, implementors of this trait record the requested output length in
+/// [`new`][Self::new]/[`new_from_slice`][Self::new_from_slice] and write
+/// exactly that many bytes in [`finalize_variable`][Self::finalize_variable].
+#[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
+pub trait VariableOutputMac: KeySizeUser + Update + VariableOutputMacMarker + Sized {
+    /// Maximum size of output in bytes supported by the algorithm.
+    const MAX_OUTPUT_SIZE: usize;
+
+    /// Create new value from fixed size key and requested output length in bytes.
+    ///
+    /// Returns [`InvalidLength`] if `output_size` is outside the range
+    /// supported by the algorithm.
+    fn new(key: &Key<Self>, output_size: usize) -> Result<Self, InvalidLength>;
+
+    /// Create new value from variable size key and requested output length in bytes.
+    ///
+    /// Returns [`InvalidLength`] if `key` or `output_size` have an invalid length.
+    fn new_from_slice(key: &[u8], output_size: usize) -> Result<Self, InvalidLength> {
+        if key.len() != Self::KeySize::USIZE {
+            return Err(InvalidLength);
+        }
+        Self::new(GenericArray::from_slice(key), output_size)
+    }
+
+    /// Output length in bytes requested when this value was constructed.
+    fn output_size(&self) -> usize;
+
+    /// Obtain the result of a [`VariableOutputMac`] computation, writing
+    /// exactly `out.len()` bytes and consuming the instance.
+    ///
+    /// Returns [`InvalidLength`] if `out.len()` does not match the length
+    /// requested at construction.
+    fn finalize_variable(self, out: &mut [u8]) -> Result<(), InvalidLength>;
+
+    /// Obtain the result of a [`VariableOutputMac`] computation as a
+    /// [`CtVariableOutput`], so callers get the same timing-safe equality
+    /// guarantees that [`CtOutput`] provides for fixed-size [`Mac`] tags.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_boxed(self) -> CtVariableOutput<Box<[u8]>> {
+        let mut buf = vec![0u8; self.output_size()].into_boxed_slice();
+        self.finalize_variable(&mut buf)
+            .expect("output_size() bytes is always a valid finalize_variable length");
+        CtVariableOutput::new(buf)
+    }
+
+    /// Check if tag/code value is correct for the processed input.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn verify_variable(self, tag: &[u8]) -> Result<(), MacError> {
+        if tag.len() != self.output_size() {
+            return Err(MacError);
+        }
+        if self.finalize_boxed() == CtVariableOutput::new(tag) {
+            Ok(())
+        } else {
+            Err(MacError)
+        }
+    }
+}
+
+/// Variable-size output value which provides a safe [`Eq`] implementation
+/// that runs in constant time.
+///
+/// Unlike [`CtOutput`], which wraps a fixed-size [`Output`], this type wraps
+/// any byte buffer (e.g. `&[u8]` or a boxed slice), making it useful for
+/// implementing [`VariableOutputMac`] algorithms.
+#[derive(Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
+pub struct CtVariableOutput<T> {
+    bytes: T,
+}
+
+impl<T: AsRef<[u8]>> CtVariableOutput<T> {
+    /// Create a new [`CtVariableOutput`] value.
+    #[inline(always)]
+    pub fn new(bytes: T) -> Self {
+        Self { bytes }
+    }
+
+    /// Get the inner buffer this type wraps.
+    #[inline(always)]
+    pub fn into_bytes(self) -> T {
+        self.bytes
+    }
+}
+
+impl<T: AsRef<[u8]>> ConstantTimeEq for CtVariableOutput<T> {
+    #[inline(always)]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.bytes.as_ref().ct_eq(other.bytes.as_ref())
+    }
+}
+
+impl<T: AsRef<[u8]>, U: AsRef<[u8]>> PartialEq<CtVariableOutput<U>> for CtVariableOutput<T> {
+    #[inline(always)]
+    fn eq(&self, other: &CtVariableOutput<U>) -> bool {
+        self.bytes.as_ref().ct_eq(other.bytes.as_ref()).unwrap_u8() == 1
+    }
+}
+
+impl<T: AsRef<[u8]>> Eq for CtVariableOutput<T> {}
+
+/// Trait for keyed Message Authentication algorithms which squeeze their tag
+/// from an extendable-output function (XOF), such as KMACXOF128/256.
+///
+/// Unlike [`VariableOutputMac`], whose tag length is fixed once chosen,
+/// implementors of this trait produce an unbounded stream of tag material
+/// which callers pull incrementally through a [`XofReader`].
+#[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
+pub trait XofMac: KeySizeUser + Update + Sized {
+    /// Reader returned by [`finalize_xof`][Self::finalize_xof].
+    type Reader: XofReader;
+
+    /// Retrieve the MAC computation as a [`XofReader`], consuming the instance.
+    ///
+    /// Once [`read`][XofReader::read] has been called on the returned reader,
+    /// the absorb phase is finalized: the reader may only be squeezed for
+    /// further output, and the original [`XofMac`] instance is gone.
+    fn finalize_xof(self) -> Self::Reader;
+
+    /// Retrieve the MAC computation as a [`XofReader`] and reset the instance
+    /// to its initial state.
+    fn finalize_xof_reset(&mut self) -> Self::Reader
+    where
+        Self: Reset;
+}